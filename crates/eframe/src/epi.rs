@@ -0,0 +1,232 @@
+//! Types that a user of `eframe` interacts with when implementing an app.
+
+/// Implement this trait to write an app that can be run with [`crate::run_native`].
+///
+/// `T` is the type of payload the app can receive from other threads via
+/// [`Frame::create_user_event_proxy`]; apps that don't need this can ignore it, as it
+/// defaults to `()`.
+pub trait App<T = ()> {
+    /// Called each time the UI needs repainting, which may be many times per second.
+    ///
+    /// Put your widgets into a [`egui::SidePanel`], [`egui::TopBottomPanel`],
+    /// [`egui::CentralPanel`], [`egui::Window`] or [`egui::Area`].
+    fn update(&mut self, ctx: &egui::Context, frame: &mut Frame<T>);
+
+    /// Called once on startup, after the app state has been restored, if applicable.
+    fn setup(
+        &mut self,
+        _ctx: &egui::Context,
+        _frame: &mut Frame<T>,
+        _storage: Option<&dyn crate::Storage>,
+    ) {
+    }
+
+    /// If `true`, a warm-up call to [`Self::update`] will be issued before the real
+    /// first frame, to allow for things like JIT compiler warm-up.
+    fn warm_up_enabled(&self) -> bool {
+        false
+    }
+
+    /// Called on shutdown, and perhaps at regular intervals.
+    ///
+    /// This is a good place to save application state, using the provided [`crate::Storage`].
+    fn save(&mut self, _storage: &mut dyn crate::Storage) {}
+
+    /// Called once the native window is about to be destroyed.
+    fn on_exit(&mut self) {}
+
+    /// The application is about to be suspended.
+    ///
+    /// On Android and iOS this means the GPU surface is going away; eframe has already
+    /// dropped its render surface when this is called. Any renderer resources the app
+    /// owns outside of the GPU backend should be dropped
+    /// or paused here. No repaints will be requested while suspended, so this is
+    /// also a good place to stop background timers.
+    fn on_suspend(&mut self) {}
+
+    /// The application has been resumed after being suspended. Also called once on
+    /// startup, when the render surface is first created.
+    ///
+    /// This is called after eframe has (re-)created the GPU surface, so it is safe to
+    /// resume any rendering or repaint timers here.
+    fn on_resume(&mut self) {}
+
+    /// Called with a payload sent from another thread via a cloned
+    /// [`Frame::create_user_event_proxy`], before the next [`Self::update`].
+    fn on_user_event(&mut self, _payload: T) {}
+}
+
+/// What egui state to persist.
+pub trait Storage {
+    /// Get the value for the given key.
+    fn get_string(&self, key: &str) -> Option<String>;
+
+    /// Set the value for the given key.
+    fn set_string(&mut self, key: &str, value: String);
+
+    /// Store all the values in [`Self::set_string`] to disk or whatever medium this [`Storage`] uses.
+    fn flush(&mut self);
+}
+
+/// Represents the surroundings of your app.
+///
+/// It provides methods to inspect the surroundings (are we on the web?), and to change
+/// how you are shown, e.g. the size of the app.
+pub struct Frame<T = ()> {
+    /// A handle to the egui context, used to tell egui what's happening and to get back rendering.
+    pub(crate) egui_ctx: egui::Context,
+
+    /// A cloneable handle to the event loop, so background threads (network, file IO, ...)
+    /// can wake eframe up and deliver it data. See [`Self::create_user_event_proxy`].
+    pub(crate) event_loop_proxy: winit::event_loop::EventLoopProxy<crate::UserEvent<T>>,
+}
+
+impl<T> Frame<T> {
+    /// Get a cloneable, thread-safe handle that other threads can use to wake up the
+    /// event loop and deliver a `T` to [`App::on_user_event`].
+    ///
+    /// ```no_run
+    /// # struct MyApp;
+    /// # impl eframe::App for MyApp {
+    /// #   fn update(&mut self, _ctx: &egui::Context, _frame: &mut eframe::Frame) {}
+    /// # }
+    /// # fn example(frame: &eframe::Frame) {
+    /// let proxy = frame.create_user_event_proxy();
+    /// std::thread::spawn(move || {
+    ///     // ... do some work on a background thread ...
+    ///     let _ = proxy.send_event(eframe::UserEvent::UserData(()));
+    /// });
+    /// # }
+    /// ```
+    pub fn create_user_event_proxy(&self) -> winit::event_loop::EventLoopProxy<crate::UserEvent<T>> {
+        self.event_loop_proxy.clone()
+    }
+
+    /// Drain the MIME-typed drag-and-drop events ([`DragStarted`](crate::native::drag_and_drop::DragAndDropEvent::DragStarted),
+    /// [`DragOver`](crate::native::drag_and_drop::DragAndDropEvent::DragOver), ...) queued
+    /// since the last call, for widgets registered via
+    /// [`crate::native::drag_and_drop::DragAndDropWidgetExt`].
+    pub fn take_drag_and_drop_events(&self) -> Vec<crate::native::drag_and_drop::DragAndDropEvent> {
+        crate::native::drag_and_drop::with_registry(&self.egui_ctx, |registry| registry.take_events())
+    }
+
+    /// Construct a [`Frame`]. Called by the native runner once per frame; apps never
+    /// need to construct this themselves.
+    pub(crate) fn new(
+        egui_ctx: egui::Context,
+        event_loop_proxy: winit::event_loop::EventLoopProxy<crate::UserEvent<T>>,
+    ) -> Self {
+        Self {
+            egui_ctx,
+            event_loop_proxy,
+        }
+    }
+}
+
+/// Information given to your app on the very first frame, before it is created.
+pub struct CreationContext<'s, T = ()> {
+    /// The egui Context.
+    pub egui_ctx: egui::Context,
+
+    /// Local storage, if enabled for this app.
+    pub storage: Option<&'s dyn Storage>,
+
+    /// A cloneable handle to the event loop, so the app can stash it away and hand it
+    /// to background threads. See [`Frame::create_user_event_proxy`].
+    pub event_loop_proxy: winit::event_loop::EventLoopProxy<crate::UserEvent<T>>,
+}
+
+impl<'s, T> CreationContext<'s, T> {
+    /// Construct a [`CreationContext`]. Called by the native runner before the app is
+    /// created; apps never need to construct this themselves.
+    pub(crate) fn new(
+        egui_ctx: egui::Context,
+        storage: Option<&'s dyn Storage>,
+        event_loop_proxy: winit::event_loop::EventLoopProxy<crate::UserEvent<T>>,
+    ) -> Self {
+        Self {
+            egui_ctx,
+            storage,
+            event_loop_proxy,
+        }
+    }
+}
+
+/// Options controlling how the [`crate::App`] is run, on native.
+pub struct NativeOptions {
+    /// How should eframe decide when to repaint?
+    ///
+    /// Defaults to [`UpdateMode::Reactive`], so that eframe only wakes up for input,
+    /// explicit repaint requests and its `max_wait` timeout (one second), which is the
+    /// right choice for most apps and is
+    /// easiest on battery life.
+    pub update_mode: UpdateMode,
+
+    /// Whether to follow the system's dark/light theme.
+    pub follow_system_theme: bool,
+}
+
+impl Default for NativeOptions {
+    fn default() -> Self {
+        Self {
+            update_mode: UpdateMode::default(),
+            follow_system_theme: true,
+        }
+    }
+}
+
+/// Controls how eframe decides when to wake up and repaint, and therefore how the
+/// native event loop's `ControlFlow` is driven.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UpdateMode {
+    /// Repaint as soon as possible, every frame. Useful for games and other
+    /// continuously-animating apps.
+    Continuous,
+
+    /// Only repaint when egui detects that something has changed, or when an event
+    /// that is relevant to the app comes in.
+    ///
+    /// This is the better choice for a GUI app, as it saves CPU and battery.
+    Reactive {
+        /// Wake up and repaint when a window event (keyboard, mouse, resize, ...) comes in.
+        wait_for_window_events: bool,
+
+        /// Wake up and repaint when a raw device event (e.g. background mouse-motion
+        /// deltas) comes in. Most apps should leave this `false`, since these events
+        /// are not relevant unless the window is actually focused.
+        wait_for_device_events: bool,
+
+        /// Wake up and repaint when a [`crate::UserEvent`] (e.g. a repaint request
+        /// from another thread) comes in.
+        wait_for_user_events: bool,
+
+        /// The longest eframe will sleep before waking up on its own, even if nothing
+        /// else has asked for a repaint.
+        max_wait: std::time::Duration,
+    },
+}
+
+impl Default for UpdateMode {
+    fn default() -> Self {
+        Self::Reactive {
+            wait_for_window_events: true,
+            wait_for_device_events: false,
+            wait_for_user_events: true,
+            max_wait: std::time::Duration::from_secs(1),
+        }
+    }
+}
+
+impl UpdateMode {
+    /// A [`Self::Reactive`] preset tuned for battery-sensitive desktop apps: it only
+    /// wakes up for window and user events, never for raw device events, and is
+    /// willing to sleep for a long time in between.
+    pub fn reactive_low_power() -> Self {
+        Self::Reactive {
+            wait_for_window_events: true,
+            wait_for_device_events: false,
+            wait_for_user_events: true,
+            max_wait: std::time::Duration::from_secs(10),
+        }
+    }
+}
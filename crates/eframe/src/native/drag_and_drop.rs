@@ -0,0 +1,399 @@
+//! MIME-typed drag-and-drop, layered on top of winit's built-in file hover/drop events.
+//!
+//! Winit only tells us about whole files being dragged over or dropped onto a window
+//! (`WindowEvent::HoveredFile` / `DroppedFile`). This module adds a typed subsystem on
+//! top of that so a widget can advertise itself as a *drag source* for one or more MIME
+//! types with lazily-computed data, and a drop zone can declare which MIME types it
+//! accepts and receive the negotiated bytes. The existing file-drop path is kept as-is
+//! and is additionally surfaced through [`DragAndDropEvent::Drop`] with the
+//! `text/uri-list` MIME type, so apps can migrate to the unified API at their own pace.
+//!
+//! The [`DragAndDropState`] lives in [`egui::Context`]'s temp data, keyed by a single
+//! well-known [`egui::Id`], so widgets can register themselves via [`DragAndDropWidgetExt`]
+//! without needing a [`crate::Frame`] reference, and the native runner can drain queued
+//! events from the same place each frame.
+//!
+//! Note: dragging *out* of the window into another application requires OS-level
+//! support (OLE on Windows, NSPasteboard on macOS, XDND on X11/Wayland) that winit does
+//! not currently expose. This module provides the data model and in-window plumbing;
+//! wiring it up to real cross-application drag-out is tracked as follow-up work.
+
+use std::collections::HashMap;
+
+/// A MIME type, e.g. `text/plain` or `image/png`. Kept as an owned string rather than an
+/// enum so apps can register custom, non-standard types.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MimeType(pub String);
+
+impl MimeType {
+    /// The `text/plain` MIME type.
+    pub fn text_plain() -> Self {
+        Self("text/plain".to_owned())
+    }
+
+    /// The `text/uri-list` MIME type, used by the existing file-drop path.
+    pub fn text_uri_list() -> Self {
+        Self("text/uri-list".to_owned())
+    }
+
+    /// The `image/png` MIME type.
+    pub fn image_png() -> Self {
+        Self("image/png".to_owned())
+    }
+}
+
+impl From<&str> for MimeType {
+    fn from(s: &str) -> Self {
+        Self(s.to_owned())
+    }
+}
+
+/// A widget-registered source of draggable data.
+///
+/// The data itself is produced lazily by `provider`, so a widget doesn't have to
+/// serialize e.g. a whole image unless the drag is actually accepted somewhere.
+pub struct DragSource {
+    /// The [`egui::Id`] of the widget that registered this source.
+    pub id: egui::Id,
+
+    /// The MIME types this source can provide data as, in order of preference.
+    pub mimes: Vec<MimeType>,
+
+    /// Produce the bytes for the given MIME type, which is guaranteed to be one of
+    /// [`Self::mimes`]. Called once the drag is accepted by a target.
+    pub provider: Box<dyn Fn(&MimeType) -> Vec<u8>>,
+}
+
+/// A registered drop target that only accepts a subset of MIME types.
+#[derive(Clone, Debug)]
+pub struct DropTarget {
+    /// The [`egui::Id`] of the widget that registered this target.
+    pub id: egui::Id,
+
+    /// The MIME types this target is willing to accept.
+    pub accepted_mimes: Vec<MimeType>,
+}
+
+impl DropTarget {
+    fn best_match<'m>(&self, offered: &'m [MimeType]) -> Option<&'m MimeType> {
+        offered.iter().find(|mime| self.accepted_mimes.contains(mime))
+    }
+}
+
+/// New, MIME-aware drag-and-drop events, delivered alongside the existing
+/// `egui::Event::HoveredFile` / `DroppedFile` events.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DragAndDropEvent {
+    /// A drag has begun from a registered [`DragSource`].
+    DragStarted,
+
+    /// The drag is currently over a registered [`DropTarget`], which can accept one of
+    /// these MIME types, or a file from outside is hovering over the window (offered as
+    /// `text/uri-list`).
+    DragOver {
+        /// The MIME types on offer, in the source's preferred order.
+        mimes: Vec<MimeType>,
+    },
+
+    /// The drag was dropped onto a target that accepted `mime`, with the negotiated bytes,
+    /// or a file from outside was dropped onto the window (as a `text/uri-list` URI).
+    Drop {
+        /// Which of the offered MIME types was negotiated.
+        mime: MimeType,
+        /// The data, produced by the source's `provider` closure.
+        bytes: Vec<u8>,
+    },
+
+    /// The drag left the window, or was otherwise cancelled, without being dropped.
+    DragCancelled,
+}
+
+/// Tracks the registered drag sources/drop targets and the in-progress drag for a
+/// single viewport.
+#[derive(Default)]
+pub struct DragAndDropState {
+    sources: HashMap<egui::Id, DragSource>,
+    targets: HashMap<egui::Id, DropTarget>,
+    /// The source currently being dragged, if any. Only ever set by [`Self::start_drag`]
+    /// and cleared by [`Self::drop_onto`] or [`Self::cancel`], so [`Self::drop_onto`] can
+    /// validate a drop against the drag it's actually completing.
+    active_source: Option<egui::Id>,
+    pending_events: Vec<DragAndDropEvent>,
+}
+
+impl DragAndDropState {
+    /// Register (or re-register) `source` as a drag source, overwriting any previous
+    /// registration under the same [`egui::Id`].
+    pub fn register_source(&mut self, source: DragSource) {
+        self.sources.insert(source.id, source);
+    }
+
+    /// Register (or re-register) `target` as a drop target, overwriting any previous
+    /// registration under the same [`egui::Id`].
+    pub fn register_target(&mut self, target: DropTarget) {
+        self.targets.insert(target.id, target);
+    }
+
+    /// Begin a drag from the registered source `source_id`, if it's actually registered.
+    pub fn start_drag(&mut self, source_id: egui::Id) -> Option<DragAndDropEvent> {
+        self.sources.contains_key(&source_id).then(|| {
+            self.active_source = Some(source_id);
+            DragAndDropEvent::DragStarted
+        })
+    }
+
+    /// The in-progress drag is currently hovering over the registered target `target_id`;
+    /// returns `DragOver` with the MIME types that target is able to accept, if any
+    /// overlap with what the active source is offering.
+    pub fn hover(&self, target_id: egui::Id) -> Option<DragAndDropEvent> {
+        let source = self.sources.get(&self.active_source?)?;
+        let target = self.targets.get(&target_id)?;
+        let accepted: Vec<MimeType> = source
+            .mimes
+            .iter()
+            .filter(|mime| target.accepted_mimes.contains(mime))
+            .cloned()
+            .collect();
+        (!accepted.is_empty()).then_some(DragAndDropEvent::DragOver { mimes: accepted })
+    }
+
+    /// Complete the in-progress drag onto the registered target `target_id`, calling the
+    /// dragged source's `provider` for the best mutually-acceptable MIME type.
+    ///
+    /// Returns `None` (and leaves the drag active) if there is no in-progress drag, if
+    /// `target_id` isn't a registered target, or if source and target share no MIME type
+    /// — a caller can retry the drop elsewhere instead of having the drag silently die.
+    pub fn drop_onto(&mut self, target_id: egui::Id) -> Option<DragAndDropEvent> {
+        let source = self.sources.get(&self.active_source?)?;
+        let target = self.targets.get(&target_id)?;
+        let mime = target.best_match(&source.mimes)?.clone();
+        let bytes = (source.provider)(&mime);
+        self.active_source = None;
+        Some(DragAndDropEvent::Drop { mime, bytes })
+    }
+
+    /// The in-progress drag left the window, or was otherwise cancelled, without being
+    /// dropped.
+    pub fn cancel(&mut self) -> Option<DragAndDropEvent> {
+        self.active_source.take().map(|_| DragAndDropEvent::DragCancelled)
+    }
+
+    /// Queue `event` to be delivered to the app on the next [`Self::take_events`].
+    pub(crate) fn note_event(&mut self, event: DragAndDropEvent) {
+        self.pending_events.push(event);
+    }
+
+    /// Drain the events queued since the last call.
+    pub(crate) fn take_events(&mut self) -> Vec<DragAndDropEvent> {
+        std::mem::take(&mut self.pending_events)
+    }
+}
+
+fn registry_id() -> egui::Id {
+    egui::Id::new("eframe::native::drag_and_drop::DragAndDropState")
+}
+
+/// Run `f` against the [`DragAndDropState`] stored in `ctx`'s temp data, creating it on
+/// first use.
+pub(crate) fn with_registry<R>(ctx: &egui::Context, f: impl FnOnce(&mut DragAndDropState) -> R) -> R {
+    ctx.data_mut(|data| f(data.get_temp_mut_or_insert_with(registry_id(), DragAndDropState::default)))
+}
+
+/// Widget-facing registration API for the drag-and-drop subsystem, implemented for
+/// [`egui::Response`] so a widget can opt in with a single method call after showing
+/// itself.
+pub trait DragAndDropWidgetExt {
+    /// Register `self` as a drag source offering `mimes`, lazily providing data via
+    /// `provider` once a drop is accepted. Starts the drag the frame the response
+    /// reports [`egui::Response::drag_started`].
+    fn dnd_drag_source(
+        self,
+        mimes: impl IntoIterator<Item = MimeType>,
+        provider: impl Fn(&MimeType) -> Vec<u8> + 'static,
+    ) -> Self;
+
+    /// Register `self` as a drop target accepting `mimes`.
+    fn dnd_drop_target(self, mimes: impl IntoIterator<Item = MimeType>) -> Self;
+}
+
+impl DragAndDropWidgetExt for egui::Response {
+    fn dnd_drag_source(
+        self,
+        mimes: impl IntoIterator<Item = MimeType>,
+        provider: impl Fn(&MimeType) -> Vec<u8> + 'static,
+    ) -> Self {
+        let mimes: Vec<MimeType> = mimes.into_iter().collect();
+        with_registry(&self.ctx, |registry| {
+            registry.register_source(DragSource {
+                id: self.id,
+                mimes,
+                provider: Box::new(provider),
+            });
+            if self.drag_started() {
+                if let Some(event) = registry.start_drag(self.id) {
+                    registry.note_event(event);
+                }
+            }
+        });
+        self
+    }
+
+    fn dnd_drop_target(self, mimes: impl IntoIterator<Item = MimeType>) -> Self {
+        // Whether the drag ends on this widget this frame, so a release while hovered
+        // completes the drop instead of just previewing it with `DragOver`.
+        let released = self.ctx.input(|i| i.pointer.any_released());
+        with_registry(&self.ctx, |registry| {
+            registry.register_target(DropTarget {
+                id: self.id,
+                accepted_mimes: mimes.into_iter().collect(),
+            });
+            if self.hovered() {
+                if released {
+                    if let Some(event) = registry.drop_onto(self.id) {
+                        registry.note_event(event);
+                    }
+                } else if let Some(event) = registry.hover(self.id) {
+                    registry.note_event(event);
+                }
+            }
+        });
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(id: egui::Id, mimes: &[MimeType]) -> DragSource {
+        DragSource {
+            id,
+            mimes: mimes.to_vec(),
+            provider: Box::new(|mime| mime.0.clone().into_bytes()),
+        }
+    }
+
+    fn target(id: egui::Id, accepted: &[MimeType]) -> DropTarget {
+        DropTarget {
+            id,
+            accepted_mimes: accepted.to_vec(),
+        }
+    }
+
+    #[test]
+    fn start_drag_requires_registration() {
+        let mut state = DragAndDropState::default();
+        assert_eq!(state.start_drag(egui::Id::new("unregistered")), None);
+    }
+
+    #[test]
+    fn hover_with_no_active_drag_is_none() {
+        let mut state = DragAndDropState::default();
+        let target_id = egui::Id::new("target");
+        state.register_target(target(target_id, &[MimeType::text_plain()]));
+        assert_eq!(state.hover(target_id), None);
+    }
+
+    #[test]
+    fn hover_reports_only_the_shared_mimes() {
+        let mut state = DragAndDropState::default();
+        let source_id = egui::Id::new("source");
+        let target_id = egui::Id::new("target");
+        state.register_source(source(
+            source_id,
+            &[MimeType::text_plain(), MimeType::image_png()],
+        ));
+        state.register_target(target(target_id, &[MimeType::image_png()]));
+        state.start_drag(source_id);
+
+        assert_eq!(
+            state.hover(target_id),
+            Some(DragAndDropEvent::DragOver {
+                mimes: vec![MimeType::image_png()]
+            })
+        );
+    }
+
+    #[test]
+    fn hover_with_no_overlap_is_none() {
+        let mut state = DragAndDropState::default();
+        let source_id = egui::Id::new("source");
+        let target_id = egui::Id::new("target");
+        state.register_source(source(source_id, &[MimeType::text_plain()]));
+        state.register_target(target(target_id, &[MimeType::image_png()]));
+        state.start_drag(source_id);
+
+        assert_eq!(state.hover(target_id), None);
+    }
+
+    #[test]
+    fn drop_onto_without_active_drag_is_none() {
+        let mut state = DragAndDropState::default();
+        let target_id = egui::Id::new("target");
+        state.register_target(target(target_id, &[MimeType::text_plain()]));
+        assert_eq!(state.drop_onto(target_id), None);
+    }
+
+    #[test]
+    fn drop_onto_unregistered_target_is_none_and_keeps_the_drag_active() {
+        let mut state = DragAndDropState::default();
+        let source_id = egui::Id::new("source");
+        state.register_source(source(source_id, &[MimeType::text_plain()]));
+        state.start_drag(source_id);
+
+        assert_eq!(state.drop_onto(egui::Id::new("unregistered")), None);
+        // The drag should still be active, so a subsequent drop on the right target works.
+        let target_id = egui::Id::new("target");
+        state.register_target(target(target_id, &[MimeType::text_plain()]));
+        assert!(state.drop_onto(target_id).is_some());
+    }
+
+    #[test]
+    fn drop_onto_calls_the_sources_provider_with_the_negotiated_mime() {
+        let mut state = DragAndDropState::default();
+        let source_id = egui::Id::new("source");
+        let target_id = egui::Id::new("target");
+        state.register_source(source(
+            source_id,
+            &[MimeType::text_plain(), MimeType::image_png()],
+        ));
+        state.register_target(target(target_id, &[MimeType::image_png()]));
+        state.start_drag(source_id);
+
+        assert_eq!(
+            state.drop_onto(target_id),
+            Some(DragAndDropEvent::Drop {
+                mime: MimeType::image_png(),
+                bytes: MimeType::image_png().0.into_bytes(),
+            })
+        );
+        // The drag is consumed: dropping again finds nothing active.
+        assert_eq!(state.drop_onto(target_id), None);
+    }
+
+    #[test]
+    fn cancel_clears_the_active_drag() {
+        let mut state = DragAndDropState::default();
+        let source_id = egui::Id::new("source");
+        let target_id = egui::Id::new("target");
+        state.register_source(source(source_id, &[MimeType::text_plain()]));
+        state.register_target(target(target_id, &[MimeType::text_plain()]));
+        state.start_drag(source_id);
+
+        assert_eq!(state.cancel(), Some(DragAndDropEvent::DragCancelled));
+        assert_eq!(state.drop_onto(target_id), None);
+    }
+
+    #[test]
+    fn take_events_drains_the_queue() {
+        let mut state = DragAndDropState::default();
+        state.note_event(DragAndDropEvent::DragStarted);
+        state.note_event(DragAndDropEvent::DragCancelled);
+
+        assert_eq!(
+            state.take_events(),
+            vec![DragAndDropEvent::DragStarted, DragAndDropEvent::DragCancelled]
+        );
+        assert_eq!(state.take_events(), vec![]);
+    }
+}
@@ -0,0 +1,207 @@
+//! The concrete [`WinitApp`] that drives a single [`epi::App`] through the winit event
+//! loop.
+
+use std::sync::Arc;
+
+use winit::{
+    event::Event,
+    event_loop::{EventLoopProxy, EventLoopWindowTarget},
+    window::{Window, WindowId},
+};
+
+use egui::ViewportId;
+
+use crate::epi;
+use crate::native::drag_and_drop::with_registry;
+use crate::native::winit_integration::{
+    control_flow_for_update_mode, drag_and_drop_event_for_window_event, event_wakes_update_mode,
+    next_lifecycle, AppLifecycle, EventResult, UserEvent, WinitApp,
+};
+use crate::UpdateMode;
+
+/// The backend-specific render surface created while the app is not suspended.
+/// Dropping it (on suspend) and recreating it (on resume) is how eframe frees and
+/// reclaims GPU resources around the OS-driven lifecycle.
+pub trait RenderSurface: Sized {
+    /// Create the surface for `window`.
+    fn new(window: &Window) -> Self;
+}
+
+/// Drives a single `A: epi::App<T>` through the winit event loop.
+pub struct WinitAppRunner<A, S, T = ()> {
+    app: A,
+    window: Arc<Window>,
+    viewport_id: ViewportId,
+    egui_ctx: egui::Context,
+    event_loop_proxy: EventLoopProxy<UserEvent<T>>,
+    update_mode: UpdateMode,
+    lifecycle: AppLifecycle,
+    /// `None` exactly while [`AppLifecycle::Suspended`].
+    surface: Option<S>,
+    frame_nr: u64,
+}
+
+impl<A, S, T> WinitAppRunner<A, S, T>
+where
+    A: epi::App<T>,
+    S: RenderSurface,
+    T: Clone,
+{
+    /// Create the app (via `app_creator`, which is handed a [`epi::CreationContext`]
+    /// carrying a clone of `event_loop_proxy`) and start running it.
+    ///
+    /// The runner starts in [`AppLifecycle::Idle`], since the event loop has not
+    /// necessarily delivered a `Resumed` event yet even though `window` already exists
+    /// (e.g. on desktop, the window may be created eagerly before the loop starts
+    /// pumping). It's immediately driven through [`AppLifecycle::WillResume`] so the
+    /// render surface is created, and `App::on_resume` is called, via the exact same
+    /// state-machine transition `on_event` uses for a real winit `Resumed`, rather than
+    /// duplicating that logic here.
+    pub fn new(
+        app_creator: impl FnOnce(&epi::CreationContext<'_, T>) -> A,
+        window: Arc<Window>,
+        egui_ctx: egui::Context,
+        event_loop_proxy: EventLoopProxy<UserEvent<T>>,
+        storage: Option<&dyn crate::Storage>,
+        update_mode: UpdateMode,
+    ) -> Self {
+        let creation_context =
+            epi::CreationContext::new(egui_ctx.clone(), storage, event_loop_proxy.clone());
+        let app = app_creator(&creation_context);
+        let mut runner = Self {
+            app,
+            window,
+            viewport_id: ViewportId::ROOT,
+            egui_ctx,
+            event_loop_proxy,
+            update_mode,
+            lifecycle: AppLifecycle::Idle,
+            surface: None,
+            frame_nr: 0,
+        };
+        runner.set_lifecycle(AppLifecycle::WillResume);
+        runner
+    }
+
+    /// The `ControlFlow` the outer event loop should set once it's done handling the
+    /// current batch of events, unless an [`EventResult`] asked for something more
+    /// urgent (e.g. [`EventResult::RepaintAt`]).
+    pub fn next_control_flow(&self) -> winit::event_loop::ControlFlow {
+        control_flow_for_update_mode(&self.update_mode)
+    }
+
+    /// Build the [`epi::Frame`] handed to the app on each `update` call.
+    fn frame(&self) -> epi::Frame<T> {
+        epi::Frame::new(self.egui_ctx.clone(), self.event_loop_proxy.clone())
+    }
+}
+
+impl<A, S, T> WinitApp<T> for WinitAppRunner<A, S, T>
+where
+    A: epi::App<T>,
+    S: RenderSurface,
+    T: Clone,
+{
+    fn frame_nr(&self, viewport_id: ViewportId) -> u64 {
+        if viewport_id == self.viewport_id {
+            self.frame_nr
+        } else {
+            0
+        }
+    }
+
+    fn window(&self, window_id: WindowId) -> Option<Arc<Window>> {
+        (self.window.id() == window_id).then(|| self.window.clone())
+    }
+
+    fn window_id_from_viewport_id(&self, id: ViewportId) -> Option<WindowId> {
+        (id == self.viewport_id).then(|| self.window.id())
+    }
+
+    fn lifecycle(&self) -> AppLifecycle {
+        self.lifecycle
+    }
+
+    fn set_lifecycle(&mut self, lifecycle: AppLifecycle) {
+        match lifecycle {
+            AppLifecycle::WillSuspend => {
+                // Drop the render surface before telling the app, so `on_suspend` never
+                // observes a surface that's about to disappear out from under it.
+                self.surface = None;
+                self.app.on_suspend();
+                self.lifecycle = AppLifecycle::Suspended;
+            }
+            AppLifecycle::WillResume => {
+                self.surface = Some(S::new(&self.window));
+                self.lifecycle = AppLifecycle::Running;
+                self.app.on_resume();
+            }
+            other => self.lifecycle = other,
+        }
+    }
+
+    fn save_and_destroy(&mut self) {
+        self.app.on_exit();
+    }
+
+    fn run_ui_and_paint(
+        &mut self,
+        _event_loop: &EventLoopWindowTarget<UserEvent<T>>,
+        window_id: WindowId,
+    ) -> EventResult {
+        if self.window.id() != window_id || self.surface.is_none() {
+            // Suspended, or a stale event for a window we don't own: nothing to paint.
+            return EventResult::Wait;
+        }
+        let mut frame = self.frame();
+        self.app.update(&self.egui_ctx, &mut frame);
+
+        // All widgets have registered/updated drag-and-drop state for this frame by now,
+        // so if the pointer was released without any drop target claiming it, the drag
+        // ended without a match: cancel it rather than leaving `active_source` stuck.
+        if self.egui_ctx.input(|i| i.pointer.any_released()) {
+            with_registry(&self.egui_ctx, |registry| {
+                if let Some(event) = registry.cancel() {
+                    registry.note_event(event);
+                }
+            });
+        }
+
+        self.frame_nr += 1;
+        EventResult::Wait
+    }
+
+    fn on_event(
+        &mut self,
+        _event_loop: &EventLoopWindowTarget<UserEvent<T>>,
+        event: &Event<UserEvent<T>>,
+    ) -> crate::Result<EventResult> {
+        if let Event::UserEvent(UserEvent::UserData(payload)) = event {
+            // Deliver the payload before the next `update`, as documented on
+            // `App::on_user_event`. Whether this also wakes up a reactive app still goes
+            // through `event_wakes_update_mode` below, so `wait_for_user_events: false`
+            // is honored.
+            self.app.on_user_event(payload.clone());
+        }
+
+        if let Event::WindowEvent { window_id, event } = event {
+            if *window_id == self.window.id() {
+                if let Some(dnd_event) = drag_and_drop_event_for_window_event(event) {
+                    with_registry(&self.egui_ctx, |registry| registry.note_event(dnd_event));
+                }
+            }
+        }
+
+        if let Some(next) = next_lifecycle(self.lifecycle, event) {
+            self.set_lifecycle(next);
+        }
+
+        if event_wakes_update_mode(&self.update_mode, event) {
+            Ok(EventResult::RepaintNext(self.window.id()))
+        } else {
+            // E.g. a `DeviceEvent` mouse-motion delta while `wait_for_device_events` is
+            // off: not worth waking up a backgrounded window for.
+            Ok(EventResult::Wait)
+        }
+    }
+}
@@ -9,6 +9,8 @@ use egui::ViewportId;
 #[cfg(feature = "accesskit")]
 use egui_winit::accesskit_winit;
 
+use super::drag_and_drop::{DragAndDropEvent, MimeType};
+
 /// Create an egui context, restoring it from storage if possible.
 pub fn create_egui_context(storage: Option<&dyn crate::Storage>) -> egui::Context {
     crate::profile_function!();
@@ -31,9 +33,14 @@ pub fn create_egui_context(storage: Option<&dyn crate::Storage>) -> egui::Contex
     egui_ctx
 }
 
-/// The custom even `eframe` uses with the [`winit`] event loop.
+/// The custom event `eframe` uses with the [`winit`] event loop.
+///
+/// Generic over `T`, the type of the payload an app can send itself via
+/// [`winit::event_loop::EventLoopProxy::send_event`] from any thread, e.g. to deliver the
+/// result of a background network or file IO operation. Apps that don't need this can
+/// ignore it; it defaults to `()`.
 #[derive(Debug)]
-pub enum UserEvent {
+pub enum UserEvent<T = ()> {
     /// A repaint is requested.
     RequestRepaint {
         /// What to repaint.
@@ -49,16 +56,46 @@ pub enum UserEvent {
     /// An event related to [`accesskit`](https://accesskit.dev/).
     #[cfg(feature = "accesskit")]
     AccessKitEvent(accesskit_winit::Event),
+
+    /// Data sent to the app from another thread via [`crate::Frame::create_user_event_proxy`].
+    ///
+    /// Delivered to [`crate::App::on_user_event`] before the next `update`.
+    UserData(T),
 }
 
 #[cfg(feature = "accesskit")]
-impl From<accesskit_winit::Event> for UserEvent {
+impl<T> From<accesskit_winit::Event> for UserEvent<T> {
     fn from(inner: accesskit_winit::Event) -> Self {
         Self::AccessKitEvent(inner)
     }
 }
 
-pub trait WinitApp {
+/// The life cycle of the native application, mirroring what the OS can do to it.
+///
+/// This matters most on Android, where the GPU surface is destroyed when the app is
+/// backgrounded and must be re-created when it comes back to the foreground, but it is
+/// also useful on desktop to know when to pause any background repaint timers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AppLifecycle {
+    /// The event loop has not started running yet.
+    Idle,
+
+    /// The app is running normally, with a live render surface.
+    Running,
+
+    /// Winit has told us the app is about to be suspended; the render surface is
+    /// about to be destroyed.
+    WillSuspend,
+
+    /// The app is suspended: there is no render surface, and no repaints should happen.
+    Suspended,
+
+    /// Winit has told us the app is about to be resumed; the render surface is about
+    /// to be (re-)created.
+    WillResume,
+}
+
+pub trait WinitApp<T = ()> {
     /// The current frame number, as reported by egui.
     fn frame_nr(&self, viewport_id: ViewportId) -> u64;
 
@@ -66,18 +103,29 @@ pub trait WinitApp {
 
     fn window_id_from_viewport_id(&self, id: ViewportId) -> Option<WindowId>;
 
+    /// The current point in the application's [`AppLifecycle`].
+    fn lifecycle(&self) -> AppLifecycle;
+
+    /// Move the application to a new point in its [`AppLifecycle`].
+    ///
+    /// Transitioning into [`AppLifecycle::Suspended`] should drop the render surface, and
+    /// transitioning into [`AppLifecycle::Running`] (via [`AppLifecycle::WillResume`]) should
+    /// (re-)create it. The `epi::App::on_suspend`/`on_resume` callbacks are fired when
+    /// entering [`AppLifecycle::Suspended`] and [`AppLifecycle::Running`] respectively.
+    fn set_lifecycle(&mut self, lifecycle: AppLifecycle);
+
     fn save_and_destroy(&mut self);
 
     fn run_ui_and_paint(
         &mut self,
-        event_loop: &EventLoopWindowTarget<UserEvent>,
+        event_loop: &EventLoopWindowTarget<UserEvent<T>>,
         window_id: WindowId,
     ) -> EventResult;
 
     fn on_event(
         &mut self,
-        event_loop: &EventLoopWindowTarget<UserEvent>,
-        event: &winit::event::Event<UserEvent>,
+        event_loop: &EventLoopWindowTarget<UserEvent<T>>,
+        event: &winit::event::Event<UserEvent<T>>,
     ) -> crate::Result<EventResult>;
 }
 
@@ -103,6 +151,67 @@ pub enum EventResult {
     Exit,
 }
 
+/// Given the current [`AppLifecycle`] and an incoming winit event, work out what the next
+/// [`AppLifecycle`] should be, if the event causes a transition.
+///
+/// `winit::event::Event::Suspended` moves towards [`AppLifecycle::Suspended`] (via
+/// [`AppLifecycle::WillSuspend`], so the caller can drop the render surface first), and
+/// `winit::event::Event::Resumed` moves back towards [`AppLifecycle::Running`] (via
+/// [`AppLifecycle::WillResume`], so the caller can re-create it).
+pub fn next_lifecycle<T>(
+    current: AppLifecycle,
+    event: &winit::event::Event<T>,
+) -> Option<AppLifecycle> {
+    match event {
+        winit::event::Event::Suspended => match current {
+            AppLifecycle::Idle | AppLifecycle::Suspended => None,
+            _ => Some(AppLifecycle::WillSuspend),
+        },
+        winit::event::Event::Resumed => match current {
+            AppLifecycle::Running => None,
+            _ => Some(AppLifecycle::WillResume),
+        },
+        _ => None,
+    }
+}
+
+/// Whether `event` is relevant given the app's [`crate::UpdateMode`], i.e. whether it
+/// should cause a repaint rather than being silently dropped.
+///
+/// This is what lets a backgrounded window on a laptop ignore raw mouse-motion deltas
+/// instead of waking up and burning CPU for every `DeviceEvent`.
+pub fn event_wakes_update_mode<T>(
+    update_mode: &crate::UpdateMode,
+    event: &winit::event::Event<T>,
+) -> bool {
+    match update_mode {
+        crate::UpdateMode::Continuous => true,
+        crate::UpdateMode::Reactive {
+            wait_for_window_events,
+            wait_for_device_events,
+            wait_for_user_events,
+            ..
+        } => match event {
+            winit::event::Event::WindowEvent { .. } => *wait_for_window_events,
+            winit::event::Event::DeviceEvent { .. } => *wait_for_device_events,
+            winit::event::Event::UserEvent(_) => *wait_for_user_events,
+            _ => true,
+        },
+    }
+}
+
+/// Translate the [`crate::UpdateMode`] the app was configured with into the winit
+/// event loop's `ControlFlow`, to be used whenever the app itself didn't request an
+/// earlier repaint via [`EventResult`].
+pub fn control_flow_for_update_mode(update_mode: &crate::UpdateMode) -> winit::event_loop::ControlFlow {
+    match update_mode {
+        crate::UpdateMode::Continuous => winit::event_loop::ControlFlow::Poll,
+        crate::UpdateMode::Reactive { max_wait, .. } => {
+            winit::event_loop::ControlFlow::WaitUntil(Instant::now() + *max_wait)
+        }
+    }
+}
+
 pub fn system_theme(window: &Window, options: &crate::NativeOptions) -> Option<crate::Theme> {
     if options.follow_system_theme {
         window
@@ -115,17 +224,201 @@ pub fn system_theme(window: &Window, options: &crate::NativeOptions) -> Option<c
 
 /// Short and fast description of an event.
 /// Useful for logging and profiling.
-pub fn short_event_description(event: &winit::event::Event<UserEvent>) -> &'static str {
+pub fn short_event_description<T>(event: &winit::event::Event<UserEvent<T>>) -> &'static str {
     match event {
         winit::event::Event::UserEvent(user_event) => match user_event {
             UserEvent::RequestRepaint { .. } => "UserEvent::RequestRepaint",
             #[cfg(feature = "accesskit")]
             UserEvent::AccessKitEvent(_) => "UserEvent::AccessKitEvent",
+            UserEvent::UserData(_) => "UserEvent::UserData",
         },
         _ => egui_winit::short_generic_event_description(event),
     }
 }
 
+/// Translate winit's built-in whole-file drag-and-drop events into the richer, MIME-aware
+/// [`DragAndDropEvent`]s, so apps can handle both through the same event stream. Whole
+/// files are reported as a single `text/uri-list` offer, with the path encoded as a
+/// `file://` URI per RFC 8089.
+pub(crate) fn drag_and_drop_event_for_window_event(
+    event: &winit::event::WindowEvent<'_>,
+) -> Option<DragAndDropEvent> {
+    match event {
+        winit::event::WindowEvent::HoveredFile(_) => Some(DragAndDropEvent::DragOver {
+            mimes: vec![MimeType::text_uri_list()],
+        }),
+        winit::event::WindowEvent::DroppedFile(path) => Some(DragAndDropEvent::Drop {
+            mime: MimeType::text_uri_list(),
+            bytes: file_uri_bytes(path),
+        }),
+        winit::event::WindowEvent::HoveredFileCancelled => Some(DragAndDropEvent::DragCancelled),
+        _ => None,
+    }
+}
+
+/// Encode `path` as a percent-escaped `file://` URI, as required by the `text/uri-list`
+/// MIME type (RFC 2483). Falls back to a lossy UTF-8 conversion for any path component
+/// that isn't valid Unicode, rather than failing the drop outright.
+///
+/// Walks [`std::path::Path::components`] rather than splitting the path's string form on
+/// `'/'`, so a Windows drive-letter prefix (`C:\Users\bob\file.png`) comes out as
+/// `file:///C:/Users/bob/file.png` instead of having its colon percent-encoded and its
+/// backslashes passed through unescaped.
+fn file_uri_bytes(path: &std::path::Path) -> Vec<u8> {
+    use std::path::Component;
+
+    const RESERVED: &[u8] = b" !\"#$%&'()*+,/:;=?@[]";
+
+    // `also_allowed` lets the Windows drive-letter prefix (`C:`) through unescaped,
+    // without treating `:` as safe in ordinary path segments too.
+    fn push_escaped(uri: &mut String, component: &str, also_allowed: &[u8]) {
+        for byte in component.bytes() {
+            if byte.is_ascii_alphanumeric()
+                || also_allowed.contains(&byte)
+                || (byte.is_ascii() && !RESERVED.contains(&byte))
+            {
+                uri.push(byte as char);
+            } else {
+                uri.push_str(&format!("%{byte:02X}"));
+            }
+        }
+    }
+
+    let mut uri = String::from("file://");
+    for component in path.components() {
+        match component {
+            // The separator before this component was already pushed (or, for the very
+            // first component, `file://` needs no extra leading slash here: the next
+            // component pushes its own).
+            Component::RootDir => {}
+            Component::Prefix(prefix) => {
+                uri.push('/');
+                push_escaped(&mut uri, &prefix.as_os_str().to_string_lossy(), b":");
+            }
+            Component::Normal(part) => {
+                uri.push('/');
+                push_escaped(&mut uri, &part.to_string_lossy(), b"");
+            }
+            Component::CurDir | Component::ParentDir => {
+                uri.push('/');
+                push_escaped(&mut uri, &component.as_os_str().to_string_lossy(), b"");
+            }
+        }
+    }
+    uri.into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn next_lifecycle_suspended_only_reached_via_will_suspend() {
+        assert_eq!(
+            next_lifecycle::<()>(AppLifecycle::Running, &winit::event::Event::Suspended),
+            Some(AppLifecycle::WillSuspend)
+        );
+        assert_eq!(
+            next_lifecycle::<()>(AppLifecycle::Idle, &winit::event::Event::Suspended),
+            None
+        );
+        assert_eq!(
+            next_lifecycle::<()>(AppLifecycle::Suspended, &winit::event::Event::Suspended),
+            None
+        );
+    }
+
+    #[test]
+    fn next_lifecycle_resumed_only_fires_when_not_already_running() {
+        assert_eq!(
+            next_lifecycle::<()>(AppLifecycle::Suspended, &winit::event::Event::Resumed),
+            Some(AppLifecycle::WillResume)
+        );
+        assert_eq!(
+            next_lifecycle::<()>(AppLifecycle::Running, &winit::event::Event::Resumed),
+            None
+        );
+    }
+
+    #[test]
+    fn next_lifecycle_ignores_unrelated_events() {
+        assert_eq!(
+            next_lifecycle::<()>(AppLifecycle::Running, &winit::event::Event::NewEvents(
+                winit::event::StartCause::Init
+            )),
+            None
+        );
+    }
+
+    #[test]
+    fn event_wakes_update_mode_continuous_always_wakes() {
+        let continuous = crate::UpdateMode::Continuous;
+        assert!(event_wakes_update_mode::<()>(
+            &continuous,
+            &winit::event::Event::Suspended
+        ));
+    }
+
+    #[test]
+    fn event_wakes_update_mode_reactive_respects_device_event_flag() {
+        let reactive = crate::UpdateMode::Reactive {
+            wait_for_window_events: true,
+            wait_for_device_events: false,
+            wait_for_user_events: true,
+            max_wait: Duration::from_secs(1),
+        };
+        let device_event = winit::event::Event::DeviceEvent {
+            device_id: unsafe { winit::event::DeviceId::dummy() },
+            event: winit::event::DeviceEvent::MouseMotion { delta: (0.0, 0.0) },
+        };
+        assert!(!event_wakes_update_mode::<()>(&reactive, &device_event));
+    }
+
+    #[test]
+    fn control_flow_for_update_mode_continuous_polls() {
+        assert_eq!(
+            control_flow_for_update_mode(&crate::UpdateMode::Continuous),
+            winit::event_loop::ControlFlow::Poll
+        );
+    }
+
+    #[test]
+    fn control_flow_for_update_mode_reactive_waits_until_max_wait() {
+        let reactive = crate::UpdateMode::Reactive {
+            wait_for_window_events: true,
+            wait_for_device_events: false,
+            wait_for_user_events: true,
+            max_wait: Duration::from_secs(5),
+        };
+        match control_flow_for_update_mode(&reactive) {
+            winit::event_loop::ControlFlow::WaitUntil(when) => {
+                assert!(when > Instant::now());
+            }
+            other => panic!("expected WaitUntil, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn file_uri_bytes_encodes_a_simple_unix_path() {
+        let uri = file_uri_bytes(std::path::Path::new("/home/bob/file.png"));
+        assert_eq!(uri, b"file:///home/bob/file.png");
+    }
+
+    #[test]
+    fn file_uri_bytes_percent_encodes_reserved_characters() {
+        let uri = file_uri_bytes(std::path::Path::new("/tmp/a b#c.png"));
+        assert_eq!(uri, b"file:///tmp/a%20b%23c.png");
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn file_uri_bytes_keeps_windows_drive_letter_colon_unescaped() {
+        let uri = file_uri_bytes(std::path::Path::new(r"C:\Users\bob\file.png"));
+        assert_eq!(uri, b"file:///C:/Users/bob/file.png");
+    }
+}
+
 #[cfg(feature = "accesskit")]
 pub(crate) fn on_accesskit_window_event(
     egui_winit: &mut egui_winit::State,